@@ -21,22 +21,92 @@ pub fn impl_pymodule(
         Item::Mod(m) => m,
         other => bail_span!(other, "#[pymodule] can only be on a module declaration"),
     };
+    extend_pymodule(attr, &mut module_item, None)?;
+    Ok(module_item.into_token_stream())
+}
+
+/// Fill in `MODULE_NAME`, `extend_module` and `make_module` for `module_item`.
+///
+/// When `parent` is `Some`, this is a submodule and its `MODULE_NAME` is qualified with the
+/// parent's dotted name. Returns the module's unqualified (simple) Python name so the parent
+/// can bind it as an attribute.
+fn extend_pymodule(
+    attr: AttributeArgs,
+    module_item: &mut syn::ItemMod,
+    parent: Option<&str>,
+) -> std::result::Result<String, Diagnostic> {
     let fake_ident = Ident::new("pymodule", module_item.span());
     let module_meta =
         SimpleItemMeta::from_nested(module_item.ident.clone(), fake_ident, attr.into_iter())?;
+    let simple_name = module_meta.simple_name()?;
+    let qualified_name = match parent {
+        Some(parent) => format!("{}.{}", parent, simple_name),
+        None => simple_name.clone(),
+    };
     let mut module_context = Module {
-        name: module_meta.simple_name()?,
+        name: qualified_name.clone(),
         module_extend_items: ItemNursery::default(),
     };
+    let module_doc = attrs_to_doc(&module_item.attrs);
     let items = module_item.unbraced_content_mut()?;
 
+    if let Some(doc) = module_doc {
+        module_context.module_extend_items.add_item(
+            "__doc__".to_owned(),
+            Vec::new(),
+            quote! {
+                vm.__module_set_attr(&module, "__doc__", vm.ctx.new_str(#doc)).unwrap();
+            },
+        )?;
+    }
+
     let debug_attrs: Vec<Attribute> = vec![parse_quote!(#[RustPython derive bug!])];
     for item in items.iter_mut() {
+        // An inner `mod` marked `#[pymodule]` (or `#[pyattr] mod sub { … }`) is a submodule:
+        // recurse into it and wire it up as an attribute of this module plus an entry in
+        // `sys.modules`. The `#[pymodule]` nested meta, if any, names the submodule; the bare
+        // `#[pyattr]` spelling falls back to the `mod` identifier.
+        if let Item::Mod(inner) = item {
+            let pymodule_pos = inner.attrs.iter().position(|a| a.path.is_ident("pymodule"));
+            let pyattr_pos = inner.attrs.iter().position(|a| a.path.is_ident("pyattr"));
+            if pymodule_pos.is_some() || pyattr_pos.is_some() {
+                // Collect the submodule name args from `#[pymodule(...)]` when present, and strip
+                // both markers so they don't leak into the generated submodule.
+                let sub_args: AttributeArgs = match pymodule_pos {
+                    Some(pos) => inner.attrs.remove(pos).promoted_nested()?.into_iter().collect(),
+                    None => Vec::new(),
+                };
+                if let Some(pos) = inner.attrs.iter().position(|a| a.path.is_ident("pyattr")) {
+                    inner.attrs.remove(pos);
+                }
+                let child_ident = inner.ident.clone();
+                let child_name = extend_pymodule(sub_args, inner, Some(&qualified_name))?;
+                let child_qualified = format!("{}.{}", qualified_name, child_name);
+                // NOTE: this generated code assumes `vm.sys_module` is a public field and that
+                // `ItemProtocol::set_item(&obj, key, value, vm)` has this arity — both hold in the
+                // current `rustpython_vm`, but the UFCS call keeps the trait in scope here.
+                let wiring = quote! {
+                    let submodule = #child_ident::make_module(vm);
+                    vm.__module_set_attr(&module, #child_name, submodule.clone()).unwrap();
+                    let sys_modules = vm.get_attribute(vm.sys_module.clone(), "modules").unwrap();
+                    ::rustpython_vm::pyobject::ItemProtocol::set_item(
+                        &sys_modules, #child_qualified, submodule, vm,
+                    )
+                    .unwrap();
+                };
+                module_context
+                    .module_extend_items
+                    .add_item(child_name, Vec::new(), wiring)?;
+                continue;
+            }
+        }
+
         let mut attrs = if let Ok(attrs) = item.attrs_mut() {
             std::mem::replace(attrs, debug_attrs.clone())
         } else {
             continue;
         };
+        let doc = attrs_to_doc(&attrs);
         let mut gen_module_item = || -> Result<()> {
             let (pyitems, cfgs) = attrs_to_pyitems(&attrs, new_item)?;
             for pyitem in pyitems.iter().rev() {
@@ -45,6 +115,7 @@ pub fn impl_pymodule(
                     attrs: &mut attrs,
                     module: &mut module_context,
                     cfgs: cfgs.as_slice(),
+                    doc: doc.clone(),
                 })?;
             }
             Ok(())
@@ -80,11 +151,17 @@ pub fn impl_pymodule(
         },
     ]);
 
-    Ok(module_item.into_token_stream())
+    Ok(simple_name)
+}
+
+/// Names accepted as `#[py*]` module items: the shared `ALL_ALLOWED_NAMES` plus the
+/// module-only `pyexception` item.
+fn is_module_item(attr_name: &str) -> bool {
+    ALL_ALLOWED_NAMES.contains(&attr_name) || attr_name == "pyexception"
 }
 
 fn new_item(index: usize, attr_name: String, pyattrs: Option<Vec<usize>>) -> Box<dyn ModuleItem> {
-    assert!(ALL_ALLOWED_NAMES.contains(&attr_name.as_str()));
+    assert!(is_module_item(&attr_name));
     match attr_name.as_str() {
         "pyfunction" => Box::new(FunctionItem {
             inner: ContentItemInner { index, attr_name },
@@ -96,6 +173,9 @@ fn new_item(index: usize, attr_name: String, pyattrs: Option<Vec<usize>>) -> Box
             inner: ContentItemInner { index, attr_name },
             pyattrs: pyattrs.unwrap_or_else(Vec::new),
         }),
+        "pyexception" => Box::new(ExceptionItem {
+            inner: ContentItemInner { index, attr_name },
+        }),
         other => unreachable!("#[pymodule] doesn't accept #[{}]", other),
     }
 }
@@ -118,7 +198,7 @@ where
         };
         if attr_name == "cfg" {
             cfgs.push(attr.clone());
-        } else if ALL_ALLOWED_NAMES.contains(&attr_name.as_str()) {
+        } else if is_module_item(&attr_name) {
             break;
         }
         iter.next();
@@ -139,7 +219,7 @@ where
                 "#[py*] items must be placed under `cfgs`",
             ));
         }
-        if !ALL_ALLOWED_NAMES.contains(&attr_name.as_str()) {
+        if !is_module_item(&attr_name) {
             continue;
         } else if closed {
             return Err(syn::Error::new_spanned(
@@ -197,6 +277,11 @@ struct AttributeItem {
     inner: ContentItemInner,
 }
 
+/// #[pyexception]
+struct ExceptionItem {
+    inner: ContentItemInner,
+}
+
 impl ContentItem for FunctionItem {
     fn inner(&self) -> &ContentItemInner {
         &self.inner
@@ -215,11 +300,18 @@ impl ContentItem for AttributeItem {
     }
 }
 
+impl ContentItem for ExceptionItem {
+    fn inner(&self) -> &ContentItemInner {
+        &self.inner
+    }
+}
+
 struct ModuleItemArgs<'a> {
     item: &'a Item,
     attrs: &'a mut Vec<Attribute>,
     module: &'a mut Module,
     cfgs: &'a [Attribute],
+    doc: Option<String>,
 }
 
 impl<'a> ModuleItemArgs<'a> {
@@ -240,20 +332,55 @@ impl ModuleItem for FunctionItem {
         };
 
         let item_attr = args.attrs.remove(self.index());
+        let nested: Vec<syn::NestedMeta> = item_attr.promoted_nested()?.into_iter().collect();
+
+        // PEP 562: `#[pyfunction(module_getattr)]` / `#[pyfunction(module_dir)]` bind the
+        // function under `__getattr__` / `__dir__` instead of its own name.
+        //
+        // NOTE: this is currently inert at runtime. Storing the dunder is only half of PEP 562;
+        // the feature also requires the module type's `getattro`/`dir` slots in `obj/objmodule.rs`
+        // to fall back to `__getattr__` / `__dir__` on a missed lookup, and that VM-side change is
+        // NOT part of this crate. Until it lands, these dunders sit in the module dict unused.
+        // Keeping the macro spelling so the stdlib call sites can be written, but the request is
+        // not fully satisfied without the companion `rustpython_vm` patch.
+        if let Some(dunder) = extract_module_hook(&nested) {
+            let module = args.module_name();
+            let new_func = quote_spanned!(
+                ident.span() => vm.ctx.new_function_named(#ident, #module.to_owned(), #dunder.to_owned())
+            );
+            let item = quote! {
+                vm.__module_set_attr(&module, #dunder, #new_func).unwrap();
+            };
+            args.module
+                .module_extend_items
+                .add_item(dunder.to_owned(), args.cfgs.to_vec(), item)?;
+            return Ok(());
+        }
+
         let item_meta = SimpleItemMeta::from_nested(
             ident.clone(),
             item_attr.get_ident().unwrap().clone(),
-            item_attr.promoted_nested()?.into_iter(),
+            nested.into_iter(),
         )?;
 
         let py_name = item_meta.simple_name()?;
+        // A native function object may not carry a writable `__dict__`, so set `__doc__`
+        // best-effort through the attribute protocol rather than `set_str_attr`.
+        let doc = match &args.doc {
+            Some(doc) => quote! {
+                let _ = vm.set_attr(&new_func, "__doc__", vm.ctx.new_str(#doc));
+            },
+            None => quote!(),
+        };
         let item = {
             let module = args.module_name();
             let new_func = quote_spanned!(
                 ident.span() => vm.ctx.new_function_named(#ident, #module.to_owned(), #py_name.to_owned())
             );
             quote! {
-                vm.__module_set_attr(&module, #py_name, #new_func).unwrap();
+                let new_func = #new_func;
+                #doc
+                vm.__module_set_attr(&module, #py_name, new_func).unwrap();
             }
         };
 
@@ -312,9 +439,11 @@ impl ModuleItem for ClassItem {
             let new_class = quote_spanned!(ident.span() =>
                 #ident::make_class(&vm.ctx);
             );
+            let doc = doc_setattr(&args.doc, quote!(new_class));
             let item = quote! {
                 let new_class = #new_class;
                 new_class.set_str_attr("__module__", vm.ctx.new_str(#module_name));
+                #doc
                 vm.__module_set_attr(&module, #py_name, new_class).unwrap();
             };
 
@@ -328,6 +457,45 @@ impl ModuleItem for ClassItem {
 
 impl ModuleItem for AttributeItem {
     fn gen_module_item(&self, args: ModuleItemArgs<'_>) -> Result<()> {
+        // `#[pyattr(pysource = "...")]` freezes Python source at compile time and injects the
+        // resulting top-level functions/classes into the module's globals, so a module author
+        // can write performance-insensitive glue in Python without a startup cost.
+        let nested: Vec<syn::NestedMeta> =
+            args.attrs[self.inner.index].promoted_nested()?.into_iter().collect();
+        if let Some(source) = extract_pysource(&nested)? {
+            let key = match args.item {
+                Item::Const(syn::ItemConst { ident, .. }) => ident.to_string(),
+                other => {
+                    return Err(self.new_syn_error(
+                        other.span(),
+                        "#[pyattr(pysource = ...)] can only be on a const",
+                    ));
+                }
+            };
+            let item = quote! {
+                let frozen = ::rustpython_vm::py_compile_bytecode!(source = #source);
+                let globals = vm
+                    .get_attribute(module.clone(), "__dict__")
+                    .unwrap()
+                    .downcast()
+                    .expect("module __dict__ is not a dict");
+                let scope = ::rustpython_vm::scope::Scope::new(None, globals, vm);
+                // Run each frozen module body in the module's own scope. Executing the bytecode
+                // lets Python's own `def` / `class` statements bind real function and class objects
+                // into the module globals — scavenging `Constant::Code` would mistake class bodies
+                // and comprehension/lambda code objects for functions.
+                for (_, ::rustpython_vm::bytecode::FrozenModule { code, .. }) in frozen {
+                    let py_code = ::rustpython_vm::obj::objcode::PyCode::new(code).into_ref(vm);
+                    vm.run_code_obj(py_code, scope.clone()).unwrap();
+                }
+            };
+            args.attrs.remove(self.index());
+            args.module
+                .module_extend_items
+                .add_item(key, args.cfgs.to_vec(), item)?;
+            return Ok(());
+        }
+
         let get_py_name = |attrs: &mut Vec<Attribute>, ident: &Ident| -> Result<_> {
             let (meta_ident, nested) = attrs[self.inner.index].ident_and_promoted_nested()?;
             let item_meta =
@@ -390,3 +558,218 @@ impl ModuleItem for AttributeItem {
         Ok(())
     }
 }
+
+impl ModuleItem for ExceptionItem {
+    fn gen_module_item(&self, args: ModuleItemArgs<'_>) -> Result<()> {
+        let ident = match args.item {
+            Item::Struct(syn::ItemStruct { ident, .. }) => ident.clone(),
+            other => {
+                return Err(self.new_syn_error(other.span(), "can only be on a struct"));
+            }
+        };
+
+        let exc_attr = args.attrs.remove(self.index());
+        let nested: Vec<syn::NestedMeta> = exc_attr.promoted_nested()?.into_iter().collect();
+        let item_meta = SimpleItemMeta::from_nested(
+            ident.clone(),
+            exc_attr.get_ident().unwrap().clone(),
+            nested.clone().into_iter(),
+        )?;
+        let py_name = item_meta.simple_name()?;
+
+        // `base` names a field of the `ExceptionZoo` (`vm.ctx.exceptions`) — e.g. `value_error`,
+        // `runtime_error` — not the Python class name; it defaults to the root `exception_type`.
+        let base = extract_base(&nested)?;
+        let base = match base {
+            Some(path) => quote!(vm.ctx.exceptions.#path.clone()),
+            None => quote!(vm.ctx.exceptions.exception_type.clone()),
+        };
+
+        let module_name = args.module_name();
+        let item = quote! {
+            let base = #base;
+            let new_class = vm.ctx.new_class(#py_name, &base);
+            new_class.slots.borrow_mut().flags |=
+                ::rustpython_vm::slots::PyTpFlags::BASETYPE;
+            // Store the positional arguments in `.args`, mirroring `BaseException`.
+            // `__new__` is a plain (static) function: `type.__call__` already passes the class
+            // as the first positional argument, so it must not be wrapped as a classmethod.
+            let __new__ = vm.ctx.new_function(|cls: ::rustpython_vm::pyobject::PyClassRef, args: ::rustpython_vm::function::Args, vm: &::rustpython_vm::vm::VirtualMachine| {
+                ::rustpython_vm::exceptions::PyBaseException::new(args.into_vec(), vm)
+                    .into_ref_with_type(vm, cls)
+            });
+            new_class.set_str_attr("__new__", __new__);
+            let __init__ = vm.ctx.new_method(|zelf: ::rustpython_vm::pyobject::PyObjectRef, args: ::rustpython_vm::function::Args, vm: &::rustpython_vm::vm::VirtualMachine| {
+                vm.set_attr(&zelf, "args", vm.ctx.new_tuple(args.into_vec()))?;
+                Ok(())
+            });
+            new_class.set_str_attr("__init__", __init__);
+            new_class.set_str_attr("__module__", vm.ctx.new_str(#module_name));
+            vm.__module_set_attr(&module, #py_name, new_class).unwrap();
+        };
+
+        args.module
+            .module_extend_items
+            .add_item(py_name, args.cfgs.to_vec(), item)?;
+        Ok(())
+    }
+}
+
+/// Produce the tokens that set `__doc__` on an already-bound object, or nothing when the item
+/// carries no doc comment.
+fn doc_setattr(doc: &Option<String>, obj: TokenStream) -> TokenStream {
+    match doc {
+        Some(doc) => quote! {
+            #obj.set_str_attr("__doc__", vm.ctx.new_str(#doc));
+        },
+        None => quote!(),
+    }
+}
+
+/// Collect the `#[doc = "..."]` attributes (i.e. `///` comments) of an item into a single
+/// de-indented string, returning `None` when there are none. The common leading indentation is
+/// stripped so the text reads the same in Python's `help()` as it does in the Rust source.
+fn attrs_to_doc(attrs: &[Attribute]) -> Option<String> {
+    use syn::{Lit, Meta, MetaNameValue};
+    let mut lines: Vec<String> = Vec::new();
+    for attr in attrs {
+        if !attr.path.is_ident("doc") {
+            continue;
+        }
+        if let Ok(Meta::NameValue(MetaNameValue {
+            lit: Lit::Str(lit), ..
+        })) = attr.parse_meta()
+        {
+            lines.push(lit.value());
+        }
+    }
+    if lines.is_empty() {
+        return None;
+    }
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let doc = lines
+        .iter()
+        .map(|line| {
+            if line.len() >= indent {
+                &line[indent..]
+            } else {
+                line.as_str()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    Some(doc)
+}
+
+/// Extract the `pysource = "..."` literal from a `#[pyattr(...)]` attribute's nested meta.
+fn extract_pysource(nested: &[syn::NestedMeta]) -> Result<Option<String>> {
+    use syn::{Lit, Meta, NestedMeta};
+    for meta in nested {
+        if let NestedMeta::Meta(Meta::NameValue(name_value)) = meta {
+            if name_value.path.is_ident("pysource") {
+                return match &name_value.lit {
+                    Lit::Str(lit) => Ok(Some(lit.value())),
+                    other => Err(syn::Error::new_spanned(other, "pysource must be a string")),
+                };
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Recognize the PEP 562 module-hook flags on a `#[pyfunction(...)]` attribute and map
+/// them to the dunder the function should be bound under. Returns `None` for an ordinary
+/// function.
+fn extract_module_hook(nested: &[syn::NestedMeta]) -> Option<&'static str> {
+    use syn::{Meta, NestedMeta};
+    for meta in nested {
+        if let NestedMeta::Meta(Meta::Path(path)) = meta {
+            if path.is_ident("module_getattr") {
+                return Some("__getattr__");
+            } else if path.is_ident("module_dir") {
+                return Some("__dir__");
+            }
+        }
+    }
+    None
+}
+
+/// Extract the optional `base = "..."` from a `#[pyexception(...)]` attribute's nested meta.
+///
+/// The value must be the `ExceptionZoo` field name of the base type (`vm.ctx.exceptions.<field>`),
+/// e.g. `value_error` or `runtime_error` — *not* the Python class name (`ValueError`). An unknown
+/// field name surfaces as a missing-field error in the generated `extend_module`.
+fn extract_base(nested: &[syn::NestedMeta]) -> Result<Option<Ident>> {
+    use syn::{Lit, Meta, NestedMeta};
+    for meta in nested {
+        if let NestedMeta::Meta(Meta::NameValue(name_value)) = meta {
+            if name_value.path.is_ident("base") {
+                return match &name_value.lit {
+                    Lit::Str(lit) => Ok(Some(Ident::new(&lit.value(), lit.span()))),
+                    other => Err(syn::Error::new_spanned(other, "base must be a string")),
+                };
+            }
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn pyexception_is_a_module_item() {
+        assert!(is_module_item("pyexception"));
+        assert!(is_module_item("pyfunction"));
+        assert!(!is_module_item("derive"));
+    }
+
+    #[test]
+    fn module_hook_flags() {
+        let getattr: syn::NestedMeta = parse_quote!(module_getattr);
+        let dir: syn::NestedMeta = parse_quote!(module_dir);
+        let name: syn::NestedMeta = parse_quote!(name = "foo");
+        assert_eq!(extract_module_hook(&[getattr]), Some("__getattr__"));
+        assert_eq!(extract_module_hook(&[dir]), Some("__dir__"));
+        assert_eq!(extract_module_hook(&[name]), None);
+    }
+
+    #[test]
+    fn exception_base_defaults_and_overrides() {
+        assert!(extract_base(&[]).unwrap().is_none());
+        let base: syn::NestedMeta = parse_quote!(base = "value_error");
+        let ident = extract_base(&[base]).unwrap().unwrap();
+        assert_eq!(ident.to_string(), "value_error");
+    }
+
+    #[test]
+    fn pysource_literal() {
+        assert!(extract_pysource(&[]).unwrap().is_none());
+        let src: syn::NestedMeta = parse_quote!(pysource = "def f(): pass");
+        assert_eq!(
+            extract_pysource(&[src]).unwrap().as_deref(),
+            Some("def f(): pass"),
+        );
+    }
+
+    #[test]
+    fn doc_is_collected_and_dedented() {
+        let attrs: Vec<Attribute> = vec![
+            parse_quote!(#[doc = " first line"]),
+            parse_quote!(#[doc = " second line"]),
+        ];
+        assert_eq!(
+            attrs_to_doc(&attrs).as_deref(),
+            Some("first line\nsecond line"),
+        );
+        let none: Vec<Attribute> = vec![parse_quote!(#[inline])];
+        assert_eq!(attrs_to_doc(&none), None);
+    }
+}